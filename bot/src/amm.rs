@@ -0,0 +1,320 @@
+//! Constant-product and StableSwap curve math for quoting real DEX prices.
+
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::money::{FixedPrice, PRICE_DECIMALS};
+
+/// Which invariant a pool trades under, set per pool in `Config::dex_pools`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolKind {
+    /// Uniswap-V2/Aerodrome volatile pair: `x * y = k`.
+    Volatile,
+    /// Aerodrome/Curve-style StableSwap pair, parameterized by the
+    /// amplification coefficient `A`.
+    Stable { amplification: u64 },
+}
+
+/// Constant-product output for an input `dx` against reserves
+/// `(reserve_in, reserve_out)`, net of a proportional fee:
+/// `dy = dx*(1-f)*Rout / (Rin + dx*(1-f))`.
+pub fn constant_product_output(
+    dx: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+) -> Option<U256> {
+    if fee_bps >= 10_000 {
+        return None;
+    }
+    let dx_after_fee = dx
+        .checked_mul(U256::from(10_000 - fee_bps))?
+        .checked_div(U256::from(10_000u64))?;
+    dx_after_fee
+        .checked_mul(reserve_out)?
+        .checked_div(reserve_in.checked_add(dx_after_fee)?)
+}
+
+/// Inverse of `constant_product_output`: the input `dx` required to receive
+/// exactly `dy` out of `reserve_out`, net of the same proportional fee.
+/// Used to cap a fill at a CEX book level's remaining depth without losing
+/// the relationship between the two reserves.
+pub fn constant_product_input_for_output(
+    dy: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+) -> Option<U256> {
+    if fee_bps >= 10_000 || dy >= reserve_out {
+        return None;
+    }
+    let numerator = dy.checked_mul(reserve_in)?.checked_mul(U256::from(10_000u64))?;
+    let denominator = reserve_out
+        .checked_sub(dy)?
+        .checked_mul(U256::from(10_000 - fee_bps))?;
+    numerator.checked_div(denominator)
+}
+
+/// Marginal spot price `Rout/Rin`, expressed as a `FixedPrice` at
+/// `PRICE_DECIMALS`.
+pub fn constant_product_spot_price(reserve_in: U256, reserve_out: U256) -> Option<FixedPrice> {
+    if reserve_in.is_zero() {
+        return None;
+    }
+    let scale = U256::from(10u64).pow(U256::from(PRICE_DECIMALS));
+    let mantissa = reserve_out.checked_mul(scale)?.checked_div(reserve_in)?;
+    Some(FixedPrice::new(mantissa, PRICE_DECIMALS))
+}
+
+/// Newton iterations are capped rather than run to an exact fixed point -
+/// matches how on-chain StableSwap implementations bound gas.
+const STABLESWAP_MAX_ITERATIONS: u32 = 255;
+
+/// Solve the two-asset StableSwap invariant
+/// `A*n^n*Sum(x) + D = A*D*n^n + D^(n+1)/(n^n*Prod(x))` for `D`, by Newton
+/// iteration from `D0 = Sum(x)`. `amplification` is taken in the Curve
+/// contract convention (`A*n^(n-1)`), so `A*n^n` is computed as
+/// `amplification * n`.
+pub fn stableswap_invariant_d(balances: [U256; 2], amplification: u64) -> Option<U256> {
+    let n = U256::from(2u64);
+    let ann = U256::from(amplification).checked_mul(n)?;
+    let s = balances[0].checked_add(balances[1])?;
+    if s.is_zero() {
+        return Some(U256::zero());
+    }
+
+    let mut d = s;
+    for _ in 0..STABLESWAP_MAX_ITERATIONS {
+        let mut d_p = d;
+        for &x in &balances {
+            d_p = d_p.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+        }
+        let d_prev = d;
+
+        let numerator = ann
+            .checked_mul(s)?
+            .checked_add(d_p.checked_mul(n)?)?
+            .checked_mul(d)?;
+        let denominator = ann
+            .checked_sub(U256::one())?
+            .checked_mul(d)?
+            .checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+        d = numerator.checked_div(denominator)?;
+
+        let delta = if d > d_prev { d - d_prev } else { d_prev - d };
+        if delta <= U256::one() {
+            break;
+        }
+    }
+    Some(d)
+}
+
+/// Given the invariant `D` and a new balance `x` for one side of a two-asset
+/// stable pool, solve for the complementary balance `y` on the other side:
+/// `y <- (y^2 + c) / (2y + b - D)`, where `b = x + D/(A*n^n)` and
+/// `c = D^(n+1) / (n^n * A*n^n * x)`, with `A*n^n` computed as
+/// `amplification * n` as in `stableswap_invariant_d`.
+pub fn stableswap_get_y(x: U256, d: U256, amplification: u64) -> Option<U256> {
+    let n = U256::from(2u64);
+    let ann = U256::from(amplification).checked_mul(n)?;
+
+    let mut c = d.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+    c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+    let b = x.checked_add(d.checked_div(ann)?)?;
+
+    let mut y = d;
+    for _ in 0..STABLESWAP_MAX_ITERATIONS {
+        let y_prev = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = y
+            .checked_mul(U256::from(2u64))?
+            .checked_add(b)?
+            .checked_sub(d)?;
+        y = numerator.checked_div(denominator)?;
+
+        let delta = if y > y_prev { y - y_prev } else { y_prev - y };
+        if delta <= U256::one() {
+            break;
+        }
+    }
+    Some(y)
+}
+
+/// Stable-pool output for an input `dx` against reserves
+/// `(reserve_in, reserve_out)`, net of a proportional fee.
+pub fn stableswap_output(
+    dx: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    amplification: u64,
+    fee_bps: u32,
+) -> Option<U256> {
+    if fee_bps >= 10_000 {
+        return None;
+    }
+    let d = stableswap_invariant_d([reserve_in, reserve_out], amplification)?;
+    let dx_after_fee = dx
+        .checked_mul(U256::from(10_000 - fee_bps))?
+        .checked_div(U256::from(10_000u64))?;
+    let new_reserve_in = reserve_in.checked_add(dx_after_fee)?;
+    let new_reserve_out = stableswap_get_y(new_reserve_in, d, amplification)?;
+    reserve_out.checked_sub(new_reserve_out)
+}
+
+/// Inverse of `stableswap_output`: the input `dx` required to receive
+/// exactly `dy` out of `reserve_out`. No closed form exists for the
+/// StableSwap invariant, so this bisects on `dx` over `stableswap_output`,
+/// which is monotonically increasing in `dx`.
+pub fn stableswap_input_for_output(
+    dy: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    amplification: u64,
+    fee_bps: u32,
+) -> Option<U256> {
+    if fee_bps >= 10_000 || dy >= reserve_out {
+        return None;
+    }
+    let mut lo = U256::zero();
+    let mut hi = reserve_in.checked_mul(U256::from(100u64))?;
+    for _ in 0..STABLESWAP_MAX_ITERATIONS {
+        if lo >= hi {
+            break;
+        }
+        let mid = lo + (hi - lo) / U256::from(2u64);
+        match stableswap_output(mid, reserve_in, reserve_out, amplification, fee_bps) {
+            Some(out) if out < dy => lo = mid + U256::one(),
+            Some(_) => hi = mid,
+            None => hi = mid,
+        }
+    }
+    Some(hi)
+}
+
+/// Marginal spot price `dy/dx` for a StableSwap pool, probed with a tiny
+/// zero-fee trade rather than a closed-form derivative - consistent with
+/// how `stableswap_output` itself is only ever evaluated via Newton
+/// iteration, never in closed form.
+pub fn stableswap_spot_price(reserve_in: U256, reserve_out: U256, amplification: u64) -> Option<FixedPrice> {
+    if reserve_in.is_zero() {
+        return None;
+    }
+    let probe = (reserve_in / U256::from(1_000_000u64)).max(U256::one());
+    let dy = stableswap_output(probe, reserve_in, reserve_out, amplification, 0)?;
+    let scale = U256::from(10u64).pow(U256::from(PRICE_DECIMALS));
+    let mantissa = dy.checked_mul(scale)?.checked_div(probe)?;
+    Some(FixedPrice::new(mantissa, PRICE_DECIMALS))
+}
+
+/// Dispatches spot-price quoting to the curve `kind` actually trades under,
+/// so a `PoolKind::Stable` pool is priced off the StableSwap invariant
+/// instead of being silently treated as constant-product.
+pub fn spot_price(reserve_in: U256, reserve_out: U256, kind: PoolKind) -> Option<FixedPrice> {
+    match kind {
+        PoolKind::Volatile => constant_product_spot_price(reserve_in, reserve_out),
+        PoolKind::Stable { amplification } => stableswap_spot_price(reserve_in, reserve_out, amplification),
+    }
+}
+
+/// Dispatches swap-output quoting to `kind`'s curve.
+pub fn swap_output(dx: U256, reserve_in: U256, reserve_out: U256, fee_bps: u32, kind: PoolKind) -> Option<U256> {
+    match kind {
+        PoolKind::Volatile => constant_product_output(dx, reserve_in, reserve_out, fee_bps),
+        PoolKind::Stable { amplification } => stableswap_output(dx, reserve_in, reserve_out, amplification, fee_bps),
+    }
+}
+
+/// Dispatches swap-input-for-output quoting to `kind`'s curve.
+pub fn swap_input_for_output(
+    dy: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+    kind: PoolKind,
+) -> Option<U256> {
+    match kind {
+        PoolKind::Volatile => constant_product_input_for_output(dy, reserve_in, reserve_out, fee_bps),
+        PoolKind::Stable { amplification } => {
+            stableswap_input_for_output(dy, reserve_in, reserve_out, amplification, fee_bps)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_product_output_matches_hand_computed_quote() {
+        // 1000/1000 pool, 30bps fee, dx=100 -> dy = 100*0.997*1000/1099.7
+        let dy = constant_product_output(
+            U256::from(100u64) * U256::exp10(18),
+            U256::from(1000u64) * U256::exp10(18),
+            U256::from(1000u64) * U256::exp10(18),
+            30,
+        )
+        .unwrap();
+        let expected = 100.0 * 0.997 * 1000.0 / 1099.7;
+        let got = dy.as_u128() as f64 / 1e18;
+        assert!((got - expected).abs() < 1e-6, "got {got}, expected {expected}");
+    }
+
+    #[test]
+    fn constant_product_input_for_output_inverts_output() {
+        let reserve_in = U256::from(1000u64) * U256::exp10(18);
+        let reserve_out = U256::from(1000u64) * U256::exp10(18);
+        let dx = U256::from(100u64) * U256::exp10(18);
+        let dy = constant_product_output(dx, reserve_in, reserve_out, 30).unwrap();
+        let recovered_dx = constant_product_input_for_output(dy, reserve_in, reserve_out, 30).unwrap();
+        // Integer rounding means this recovers dx to within a tiny tolerance.
+        let diff = if recovered_dx > dx { recovered_dx - dx } else { dx - recovered_dx };
+        assert!(diff < U256::from(10u64).pow(U256::from(12u64)));
+    }
+
+    #[test]
+    fn constant_product_spot_price_without_decimals_correction_is_a_raw_ratio() {
+        // Regression guard for the decimals bug: a raw 1000 WETH / 2,500,000
+        // USDC pool (18dp / 6dp reserves) does NOT come out to $2500 unless
+        // the caller has already rescaled the reserves to a common decimal
+        // count - that normalization now happens in `monitor_dex_prices`,
+        // not in this function.
+        let reserve_in = U256::from(1000u64) * U256::exp10(18); // 1000 WETH, 18dp
+        let reserve_out = U256::from(2_500_000u64) * U256::exp10(6); // 2.5M USDC, 6dp
+        let price = constant_product_spot_price(reserve_in, reserve_out).unwrap();
+        assert!(price.to_f64_lossy() < 1e-6, "expected a tiny un-rescaled ratio, got {}", price.to_f64_lossy());
+
+        // Once both reserves are rescaled to the same decimal count, the
+        // ratio is the real dollar price.
+        let normalized_out = reserve_out * U256::exp10(12);
+        let normalized_price = constant_product_spot_price(reserve_in, normalized_out).unwrap();
+        assert!((normalized_price.to_f64_lossy() - 2500.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stableswap_invariant_d_converges_for_balanced_pool() {
+        let balances = [U256::from(1_000_000u64) * U256::exp10(18), U256::from(1_000_000u64) * U256::exp10(18)];
+        let d = stableswap_invariant_d(balances, 100).unwrap();
+        // For a perfectly balanced pool, D should equal the sum of balances.
+        assert_eq!(d, balances[0] + balances[1]);
+    }
+
+    #[test]
+    fn stableswap_output_is_close_to_one_to_one_for_balanced_pool() {
+        let reserve = U256::from(1_000_000u64) * U256::exp10(18);
+        let dx = U256::from(1_000u64) * U256::exp10(18);
+        let dy = stableswap_output(dx, reserve, reserve, 100, 0).unwrap();
+        let diff = if dy > dx { dy - dx } else { dx - dy };
+        // A deep, balanced stable pool should swap very close to 1:1.
+        assert!(diff < U256::from(10u64).pow(U256::from(15u64)));
+    }
+
+    #[test]
+    fn spot_price_dispatches_on_pool_kind() {
+        let reserve = U256::from(1_000_000u64) * U256::exp10(18);
+        let volatile = spot_price(reserve, reserve, PoolKind::Volatile).unwrap();
+        let stable = spot_price(reserve, reserve, PoolKind::Stable { amplification: 100 }).unwrap();
+        assert!((volatile.to_f64_lossy() - 1.0).abs() < 1e-9);
+        assert!((stable.to_f64_lossy() - 1.0).abs() < 1e-6);
+    }
+}
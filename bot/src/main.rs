@@ -5,11 +5,59 @@ use serde::{Deserialize, Serialize};
 use reqwest::Client;
 use ethers::prelude::*;
 use ethers::providers::{Provider, Http};
-use ethers::types::{Address, U256, TransactionRequest};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use ethers::types::{Address, Eip1559TransactionRequest, U256};
+use tokio_tungstenite::tungstenite::Message;
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
 
+mod amm;
+mod metrics;
+mod middleware;
+mod money;
+mod proxy;
+mod signer;
+mod sizing;
+mod storage;
+mod volatility;
+
+use amm::PoolKind;
+use middleware::ExecutionClient;
+use money::{Amount, FixedPrice};
+use signer::{BotSigner, SignerBackend};
+
+ethers::contract::abigen!(
+    IPoolReserves,
+    r#"[
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+    ]"#
+);
+
+/// USDC's on-chain decimals, used only for `PoolConfig::token0_decimals`/
+/// `token1_decimals` literals - reserves built from those are immediately
+/// rescaled to `money::PRICE_DECIMALS`, the scale every other `Amount` in
+/// this module (mock quantities, profit thresholds, display figures) is on.
+const USDC_DECIMALS: u32 = 6;
+
+/// Rough wall-clock time from detecting an opportunity to the trade
+/// confirming on-chain; used as the adverse-selection horizon in
+/// `volatility::effective_min_spread`/`effective_min_profit`.
+const ESTIMATED_EXECUTION_LATENCY_SECS: f64 = 2.0;
+
+fn dollars(amount: f64) -> FixedPrice {
+    amount
+        .to_string()
+        .parse()
+        .expect("mock price literal is always valid decimal")
+}
+
+/// A quantity at `money::PRICE_DECIMALS`, the scale `dex_price.reserve_in`/
+/// `reserve_out` are normalized to - so a book depth or profit threshold
+/// built from this helper compares directly against sized DEX fills
+/// instead of landing on a raw or 6-decimal scale.
+fn qty(amount: f64) -> Amount {
+    money::parse_decimal_quantity(&amount.to_string()).expect("mock/config literal is always valid decimal")
+}
+
 // Configuration
 #[derive(Debug, Clone, Deserialize)]
 struct Config {
@@ -18,9 +66,42 @@ struct Config {
     contract_address: String,
     binance_ws_url: String,
     backpack_ws_url: String,
-    min_profit_threshold: f64,
+    min_profit_threshold: Amount,
     max_gas_price: u64,
     max_slippage: f64,
+    signer_backend: SignerBackend,
+    keystore_path: Option<String>,
+    ledger_derivation_path: Option<String>,
+    dex_pools: Vec<PoolConfig>,
+    /// Address the Prometheus `/metrics` endpoint is served on.
+    metrics_addr: std::net::SocketAddr,
+    /// Path to the embedded `sled` database tracking opportunities, trade
+    /// history, and cumulative metrics across restarts.
+    db_path: String,
+    /// SOCKS5 proxy (e.g. `"127.0.0.1:9050"` for a local Tor daemon) that
+    /// all outbound RPC/exchange connections are tunneled through. `None`
+    /// dials directly.
+    socks5_proxy: Option<String>,
+    /// Base multiplier applied to realized volatility when widening the
+    /// minimum spread/profit thresholds - see `volatility::effective_min_spread`.
+    volatility_multiplier: f64,
+}
+
+/// A single on-chain pool to read reserves from. `reserve0`/`reserve1` from
+/// `getReserves()` are matched to `token_pair` order, so `token_pair`'s
+/// first leg must be the pool's `token0`, and `token0_decimals`/
+/// `token1_decimals` must be that token's on-chain decimals (e.g. 18 for
+/// WETH, 6 for USDC) so reserves of differing scale can be normalized
+/// before they're priced.
+#[derive(Debug, Clone, Deserialize)]
+struct PoolConfig {
+    venue: String,
+    pool_address: String,
+    token_pair: String,
+    fee_bps: u32,
+    kind: PoolKind,
+    token0_decimals: u32,
+    token1_decimals: u32,
 }
 
 // Price data structures
@@ -28,8 +109,14 @@ struct Config {
 struct DEXPrice {
     venue: String,
     token_pair: String,
-    price: f64,
-    liquidity: f64,
+    price: FixedPrice,
+    liquidity: Amount,
+    // Carried alongside the scalar `price` so sizing can walk the curve
+    // instead of assuming the whole trade fills at one price.
+    reserve_in: Amount,
+    reserve_out: Amount,
+    fee_bps: u32,
+    kind: PoolKind,
     timestamp: u64,
 }
 
@@ -37,24 +124,29 @@ struct DEXPrice {
 struct CEXOrderBook {
     exchange: String,
     symbol: String,
-    bids: Vec<(f64, f64)>, // (price, quantity)
-    asks: Vec<(f64, f64)>, // (price, quantity)
+    bids: Vec<(FixedPrice, Amount)>, // (price, quantity)
+    asks: Vec<(FixedPrice, Amount)>, // (price, quantity)
     timestamp: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ArbitrageOpportunity {
     id: String,
     strategy: String,
     buy_venue: String,
     sell_venue: String,
-    buy_price: f64,
-    sell_price: f64,
-    spread: f64,
-    estimated_profit: f64,
+    buy_price: FixedPrice,
+    sell_price: FixedPrice,
+    spread: FixedPrice, // relative spread, e.g. 0.01 == 1%
+    estimated_profit: Amount,
     confidence: f64,
-    trade_size: f64,
+    trade_size: Amount,
     gas_estimate: u64,
+    token_pair: String,
+    // EWMA volatility at detection time, so the confidence gate in
+    // `execute_arbitrage` can recompute the same effective min-profit
+    // threshold without needing a live tracker handle.
+    volatility: f64,
     timestamp: u64,
 }
 
@@ -62,52 +154,67 @@ struct ArbitrageOpportunity {
 struct ArbitrageBot {
     config: Config,
     provider: Arc<Provider<Http>>,
-    wallet: LocalWallet,
+    execution_client: Arc<ExecutionClient<BotSigner>>,
     client: Client,
     dex_prices: Arc<Mutex<HashMap<String, DEXPrice>>>,
     cex_prices: Arc<Mutex<HashMap<String, CEXOrderBook>>>,
     opportunities: Arc<Mutex<Vec<ArbitrageOpportunity>>>,
     metrics: Arc<Mutex<BotMetrics>>,
+    storage: Arc<storage::Storage>,
+    volatility: Arc<Mutex<volatility::VolatilityTracker>>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct BotMetrics {
     total_trades: u64,
     successful_trades: u64,
-    total_profit: f64,
-    total_volume: f64,
+    total_profit: Amount,
+    total_volume: Amount,
     avg_execution_time: f64,
     gas_used: u64,
 }
 
 impl ArbitrageBot {
     async fn new(config: Config) -> Result<Self, Box<dyn std::error::Error>> {
-        // Initialize Ethereum provider
-        let provider = Arc::new(Provider::<Http>::try_from(&config.base_rpc_url)?);
-        
-        // Initialize wallet
-        let wallet: LocalWallet = config.private_key.parse()?;
-        let wallet = wallet.with_chain_id(8453u64); // Base mainnet chain ID
-        
-        // Initialize HTTP client
-        let client = Client::new();
-        
+        // Shared HTTP client, tunneled through `socks5_proxy` if configured
+        // so the RPC provider and REST calls both leave through the same
+        // exit (e.g. a local Tor daemon on 127.0.0.1:9050).
+        let client = proxy::build_http_client(config.socks5_proxy.as_deref())?;
+
+        // Initialize Ethereum provider over that same client.
+        let rpc_url: reqwest::Url = config.base_rpc_url.parse()?;
+        let provider = Arc::new(Provider::new(Http::new_with_client(rpc_url, client.clone())));
+
+        // Initialize the configured signer backend (raw key, keystore, or Ledger).
+        let signer = signer::build_signer(&config).await?;
+
+        // Assemble the signing/broadcast stack once so the nonce manager can
+        // serialize nonces across however many trades end up in flight.
+        let execution_client = middleware::build_execution_stack(Arc::clone(&provider), signer).await?;
+
+        // Reload cumulative metrics from the embedded database so
+        // profit/volume counters survive a restart instead of resetting.
+        let storage = storage::Storage::open(&config.db_path)?;
+        let metrics = storage.load_metrics()?;
+
         Ok(ArbitrageBot {
             config,
             provider,
-            wallet,
+            execution_client,
             client,
             dex_prices: Arc::new(Mutex::new(HashMap::new())),
             cex_prices: Arc::new(Mutex::new(HashMap::new())),
             opportunities: Arc::new(Mutex::new(Vec::new())),
-            metrics: Arc::new(Mutex::new(BotMetrics::default())),
+            metrics: Arc::new(Mutex::new(metrics)),
+            storage,
+            volatility: Arc::new(Mutex::new(volatility::VolatilityTracker::new())),
         })
     }
 
     // Main bot execution loop
     async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        println!("🚀 Starting ArbiTips Bot...");
-        
+        tracing::info!("starting arbitrage bot");
+
         // Start price feed monitoring
         let dex_prices = Arc::clone(&self.dex_prices);
         let cex_prices = Arc::clone(&self.cex_prices);
@@ -116,15 +223,20 @@ impl ArbitrageBot {
         let provider = Arc::clone(&self.provider);
 
         // Start DEX price monitoring
+        let dex_pools = config.dex_pools.clone();
         let dex_task = tokio::spawn(async move {
-            Self::monitor_dex_prices(provider, dex_prices).await;
+            Self::monitor_dex_prices(provider, dex_pools, dex_prices).await;
         });
 
         // Start Binance WebSocket
         let binance_prices = Arc::clone(&self.cex_prices);
         let binance_config = config.clone();
         let binance_task = tokio::spawn(async move {
-            Self::monitor_binance_ws(binance_config.binance_ws_url, binance_prices).await;
+            Self::monitor_binance_ws(
+                binance_config.binance_ws_url,
+                binance_config.socks5_proxy,
+                binance_prices,
+            ).await;
         });
 
         // Start Backpack WebSocket (mock for now)
@@ -138,28 +250,34 @@ impl ArbitrageBot {
         let dex_prices_ref = Arc::clone(&self.dex_prices);
         let cex_prices_ref = Arc::clone(&self.cex_prices);
         let detection_config = self.config.clone();
+        let detection_provider = Arc::clone(&self.provider);
+        let detection_storage = Arc::clone(&self.storage);
+        let detection_volatility = Arc::clone(&self.volatility);
         let detection_task = tokio::spawn(async move {
             Self::detect_opportunities(
                 dex_prices_ref,
                 cex_prices_ref,
                 opportunities,
                 detection_config,
+                detection_provider,
+                detection_storage,
+                detection_volatility,
             ).await;
         });
 
         // Start execution engine
         let execution_opportunities = Arc::clone(&self.opportunities);
-        let execution_provider = Arc::clone(&self.provider);
-        let execution_wallet = self.wallet.clone();
+        let execution_client = Arc::clone(&self.execution_client);
         let execution_config = self.config.clone();
         let execution_metrics = Arc::clone(&self.metrics);
+        let execution_storage = Arc::clone(&self.storage);
         let execution_task = tokio::spawn(async move {
             Self::execute_arbitrage(
                 execution_opportunities,
-                execution_provider,
-                execution_wallet,
+                execution_client,
                 execution_config,
                 execution_metrics,
+                execution_storage,
             ).await;
         });
 
@@ -169,6 +287,13 @@ impl ArbitrageBot {
             Self::report_metrics(metrics_ref).await;
         });
 
+        // Serve Prometheus metrics for scrapers alongside the periodic
+        // human-readable summary above.
+        let metrics_addr = self.config.metrics_addr;
+        let metrics_server_task = tokio::spawn(async move {
+            metrics::serve(metrics_addr).await;
+        });
+
         // Wait for all tasks
         tokio::try_join!(
             dex_task,
@@ -176,85 +301,99 @@ impl ArbitrageBot {
             backpack_task,
             detection_task,
             execution_task,
-            metrics_task
+            metrics_task,
+            metrics_server_task
         )?;
 
         Ok(())
     }
 
-    // Monitor DEX prices via Base RPC calls
+    // Monitor DEX prices by reading pool reserves directly from Base and
+    // pricing them through the pool's own curve (constant-product for
+    // volatile pairs, StableSwap for stable pairs).
     async fn monitor_dex_prices(
         provider: Arc<Provider<Http>>,
+        pools: Vec<PoolConfig>,
         dex_prices: Arc<Mutex<HashMap<String, DEXPrice>>>,
     ) {
         let mut interval = tokio::time::interval(Duration::from_secs(5));
-        
+
         loop {
             interval.tick().await;
-            
-            // Fetch prices from different DEXs
+
             let timestamp = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
 
-            // Mock DEX price fetching (in production, call actual DEX contracts)
-            let mock_prices = vec![
-                DEXPrice {
-                    venue: "uniswap-v3-500".to_string(),
-                    token_pair: "WETH/USDC".to_string(),
-                    price: 2500.0 + (rand::random::<f64>() - 0.5) * 10.0,
-                    liquidity: 50000.0,
-                    timestamp,
-                },
-                DEXPrice {
-                    venue: "uniswap-v3-3000".to_string(),
-                    token_pair: "WETH/USDC".to_string(),
-                    price: 2501.0 + (rand::random::<f64>() - 0.5) * 8.0,
-                    liquidity: 25000.0,
-                    timestamp,
-                },
-                DEXPrice {
-                    venue: "aerodrome".to_string(),
-                    token_pair: "WETH/USDC".to_string(),
-                    price: 2499.5 + (rand::random::<f64>() - 0.5) * 12.0,
-                    liquidity: 10000.0,
-                    timestamp,
-                },
-                DEXPrice {
-                    venue: "sushiswap".to_string(),
-                    token_pair: "WETH/USDC".to_string(),
-                    price: 2502.0 + (rand::random::<f64>() - 0.5) * 15.0,
-                    liquidity: 15000.0,
+            let mut updated = 0;
+            for pool in &pools {
+                let Ok(pool_address) = pool.pool_address.parse::<Address>() else {
+                    tracing::warn!(venue = %pool.venue, address = %pool.pool_address, "invalid pool address");
+                    continue;
+                };
+
+                let contract = IPoolReserves::new(pool_address, Arc::clone(&provider));
+                let (reserve0, reserve1, _) = match contract.get_reserves().call().await {
+                    Ok(reserves) => reserves,
+                    Err(e) => {
+                        tracing::warn!(venue = %pool.venue, error = %e, "failed to read pool reserves");
+                        continue;
+                    }
+                };
+
+                // Normalize both legs to `PRICE_DECIMALS` up front so every
+                // downstream consumer - the spot price below, sizing, the
+                // CEX comparison - works with reserves on the same scale,
+                // regardless of the pool's native token decimals (e.g. 18
+                // for WETH vs. 6 for USDC).
+                let reserve_in = Amount(U256::from(reserve0)).rescale(pool.token0_decimals, money::PRICE_DECIMALS);
+                let reserve_out = Amount(U256::from(reserve1)).rescale(pool.token1_decimals, money::PRICE_DECIMALS);
+
+                // The spot price quoted here is marginal (zero-size trade);
+                // `reserve_in`/`reserve_out`/`kind` are carried alongside it
+                // so sizing can walk the actual curve for a real trade size.
+                let Some(price) = amm::spot_price(reserve_in.0, reserve_out.0, pool.kind) else {
+                    continue;
+                };
+
+                let dex_price = DEXPrice {
+                    venue: pool.venue.clone(),
+                    token_pair: pool.token_pair.clone(),
+                    price,
+                    liquidity: reserve_out,
+                    reserve_in,
+                    reserve_out,
+                    fee_bps: pool.fee_bps,
+                    kind: pool.kind,
                     timestamp,
-                },
-            ];
+                };
 
-            // Update price cache
-            let mut prices = dex_prices.lock().await;
-            for price in mock_prices {
-                prices.insert(price.venue.clone(), price);
+                let mut prices = dex_prices.lock().await;
+                prices.insert(pool.venue.clone(), dex_price);
+                updated += 1;
             }
 
-            println!("📊 Updated DEX prices: {} venues", prices.len());
+            tracing::debug!(venues = updated, "updated dex prices");
         }
     }
 
     // Monitor Binance WebSocket for real-time order book updates
     async fn monitor_binance_ws(
         ws_url: String,
+        socks5_proxy: Option<String>,
         cex_prices: Arc<Mutex<HashMap<String, CEXOrderBook>>>,
     ) {
         loop {
-            match connect_async(&ws_url).await {
-                Ok((ws_stream, _)) => {
-                    println!("🔗 Connected to Binance WebSocket");
+            match proxy::connect_websocket(&ws_url, socks5_proxy.as_deref()).await {
+                Ok(ws_stream) => {
+                    tracing::info!("connected to binance websocket");
                     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
                     // Subscribe to ETHUSDC depth stream
                     let subscribe_msg = r#"{"method":"SUBSCRIBE","params":["ethusdc@depth"],"id":1}"#;
                     if let Err(e) = ws_sender.send(Message::Text(subscribe_msg.to_string())).await {
-                        println!("❌ Failed to subscribe to Binance stream: {}", e);
+                        tracing::warn!(error = %e, "failed to subscribe to binance stream");
                         continue;
                     }
 
@@ -268,7 +407,7 @@ impl ArbitrageBot {
                                 }
                             },
                             Err(e) => {
-                                println!("❌ Binance WebSocket error: {}", e);
+                                tracing::warn!(error = %e, "binance websocket error");
                                 break;
                             }
                             _ => {}
@@ -276,7 +415,7 @@ impl ArbitrageBot {
                     }
                 }
                 Err(e) => {
-                    println!("❌ Failed to connect to Binance WebSocket: {}", e);
+                    tracing::warn!(error = %e, "failed to connect to binance websocket");
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
             }
@@ -300,12 +439,12 @@ impl ArbitrageBot {
                 exchange: "backpack".to_string(),
                 symbol: "ETHUSDC".to_string(),
                 bids: vec![
-                    (2499.5 + (rand::random::<f64>() - 0.5) * 2.0, 10.5),
-                    (2499.0 + (rand::random::<f64>() - 0.5) * 2.0, 25.2),
+                    (dollars(2499.5 + (rand::random::<f64>() - 0.5) * 2.0), qty(10.5)),
+                    (dollars(2499.0 + (rand::random::<f64>() - 0.5) * 2.0), qty(25.2)),
                 ],
                 asks: vec![
-                    (2500.5 + (rand::random::<f64>() - 0.5) * 2.0, 12.1),
-                    (2501.0 + (rand::random::<f64>() - 0.5) * 2.0, 18.7),
+                    (dollars(2500.5 + (rand::random::<f64>() - 0.5) * 2.0), qty(12.1)),
+                    (dollars(2501.0 + (rand::random::<f64>() - 0.5) * 2.0), qty(18.7)),
                 ],
                 timestamp,
             };
@@ -321,51 +460,117 @@ impl ArbitrageBot {
         cex_prices: Arc<Mutex<HashMap<String, CEXOrderBook>>>,
         opportunities: Arc<Mutex<Vec<ArbitrageOpportunity>>>,
         config: Config,
+        provider: Arc<Provider<Http>>,
+        storage: Arc<storage::Storage>,
+        volatility_tracker: Arc<Mutex<volatility::VolatilityTracker>>,
     ) {
+        const GAS_ESTIMATE: u64 = 250_000;
         let mut interval = tokio::time::interval(Duration::from_millis(500));
-        
+
         loop {
             interval.tick().await;
-            
+
             let dex_map = dex_prices.lock().await.clone();
             let cex_map = cex_prices.lock().await.clone();
-            
+
+            // One gas-price read per tick, shared across every candidate
+            // opportunity rather than refetched per venue pair.
+            let max_fee_per_gas = provider.estimate_eip1559_fees(None).await.ok().map(|(f, _)| f);
+
             let mut new_opportunities = Vec::new();
             let timestamp = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
 
-            // DEX to CEX opportunities
+            // DEX to CEX opportunities. Spread, sizing and profit math below
+            // is integer arithmetic on `FixedPrice`/`Amount`; `f64` is used
+            // only for the volatility estimate (whose thresholds are parsed
+            // back into fixed point) and for metrics/log values. The sort
+            // below compares `Amount`s, so it can never panic on a `NaN`.
+            let base_min_spread: FixedPrice = "0.001".parse().expect("valid literal"); // 0.1% floor
+            let mut vol_tracker = volatility_tracker.lock().await;
             for (dex_name, dex_price) in &dex_map {
+                // Widen the floor by realized volatility plus an
+                // adverse-selection buffer for execution latency, so the
+                // bot doesn't fire on spreads that evaporate before the tx
+                // confirms, and doesn't under-trade when the market is calm.
+                //
+                // Keyed by venue *and* pair: two venues quoting the same
+                // pair in the same tick would otherwise overwrite each
+                // other's `last_price`, making the EWMA partly measure
+                // cross-venue spread instead of time-series volatility.
+                let vol_key = format!("{dex_name}:{}", dex_price.token_pair);
+                let pair_volatility = vol_tracker.update(&vol_key, dex_price.price.to_f64_lossy());
+                let min_spread = volatility::effective_min_spread(
+                    base_min_spread,
+                    pair_volatility,
+                    ESTIMATED_EXECUTION_LATENCY_SECS,
+                    config.volatility_multiplier,
+                );
+                let min_profit = volatility::effective_min_profit(
+                    config.min_profit_threshold,
+                    pair_volatility,
+                    ESTIMATED_EXECUTION_LATENCY_SECS,
+                    config.volatility_multiplier,
+                );
+
                 for (cex_name, cex_book) in &cex_map {
-                    if let Some(best_bid) = cex_book.bids.first() {
-                        let spread = best_bid.0 - dex_price.price;
-                        let spread_pct = (spread / dex_price.price) * 100.0;
-                        
-                        if spread_pct > 0.1 { // Min 0.1% spread
-                            let trade_size = (dex_price.liquidity * 0.1).min(best_bid.1 * best_bid.0);
-                            let estimated_profit = trade_size * spread_pct / 100.0;
-                            
-                            if estimated_profit > config.min_profit_threshold {
-                                let opportunity = ArbitrageOpportunity {
-                                    id: format!("{}-{}-{}", dex_name, cex_name, timestamp),
-                                    strategy: "dex-to-cex".to_string(),
-                                    buy_venue: dex_name.clone(),
-                                    sell_venue: cex_name.clone(),
-                                    buy_price: dex_price.price,
-                                    sell_price: best_bid.0,
-                                    spread: spread_pct,
-                                    estimated_profit,
-                                    confidence: Self::calculate_confidence(spread_pct, trade_size),
-                                    trade_size,
-                                    gas_estimate: 250000,
-                                    timestamp,
-                                };
-                                
-                                new_opportunities.push(opportunity);
-                            }
+                    let Some(&(bid_price, _)) = cex_book.bids.first() else { continue };
+
+                    let Some(spread_pct) = bid_price.relative_diff(dex_price.price) else { continue };
+                    metrics::VENUE_SPREAD_PCT
+                        .with_label_values(&[dex_name.as_str(), cex_name.as_str()])
+                        .set(spread_pct.to_f64_lossy() * 100.0);
+                    if spread_pct <= min_spread {
+                        continue;
+                    }
+
+                    // Walk the AMM curve against the book's actual depth
+                    // instead of assuming a flat `liquidity * 0.1` fills
+                    // entirely at the quoted scalar price.
+                    let sized = sizing::size_against_book(
+                        dex_price.reserve_out.0,
+                        dex_price.reserve_in.0,
+                        dex_price.fee_bps,
+                        dex_price.kind,
+                        &cex_book.bids,
+                    );
+                    let Some(gross_profit) = sized.gross_profit() else { continue };
+                    if sized.amm_input == Amount::ZERO {
+                        continue;
+                    }
+
+                    let gas_cost = max_fee_per_gas
+                        .and_then(|fee| fee.checked_mul(U256::from(GAS_ESTIMATE)))
+                        .and_then(|wei| Amount(wei).checked_mul_price(dex_price.price))
+                        .unwrap_or(Amount::ZERO);
+                    let Some(estimated_profit) = gross_profit.checked_sub(gas_cost) else { continue };
+
+                    let trade_size = sized.amm_input;
+
+                    if estimated_profit > min_profit {
+                        let opportunity = ArbitrageOpportunity {
+                            id: format!("{}-{}-{}", dex_name, cex_name, timestamp),
+                            strategy: "dex-to-cex".to_string(),
+                            buy_venue: dex_name.clone(),
+                            sell_venue: cex_name.clone(),
+                            buy_price: dex_price.price,
+                            sell_price: bid_price,
+                            spread: spread_pct,
+                            estimated_profit,
+                            confidence: Self::calculate_confidence(spread_pct, trade_size),
+                            trade_size,
+                            gas_estimate: GAS_ESTIMATE,
+                            token_pair: dex_price.token_pair.clone(),
+                            volatility: pair_volatility,
+                            timestamp,
+                        };
+
+                        if let Err(e) = storage.record_opportunity(&opportunity) {
+                            tracing::warn!(error = %e, "failed to persist opportunity");
                         }
+                        new_opportunities.push(opportunity);
                     }
                 }
             }
@@ -374,14 +579,21 @@ impl ArbitrageBot {
             let mut opps = opportunities.lock().await;
             opps.clear();
             opps.extend(new_opportunities.clone());
-            opps.sort_by(|a, b| b.estimated_profit.partial_cmp(&a.estimated_profit).unwrap());
+            opps.sort_by(|a, b| b.estimated_profit.cmp(&a.estimated_profit));
             opps.truncate(10); // Keep top 10 opportunities
+            metrics::OPEN_OPPORTUNITIES.set(opps.len() as i64);
 
             if !new_opportunities.is_empty() {
-                println!("🎯 Found {} arbitrage opportunities", new_opportunities.len());
+                metrics::OPPORTUNITIES_DETECTED.inc_by(new_opportunities.len() as u64);
+                tracing::info!(count = new_opportunities.len(), "found arbitrage opportunities");
                 for opp in new_opportunities.iter().take(3) {
-                    println!("   {} -> {} | Spread: {:.2}% | Profit: ${:.2}",
-                        opp.buy_venue, opp.sell_venue, opp.spread, opp.estimated_profit);
+                    tracing::info!(
+                        buy_venue = %opp.buy_venue,
+                        sell_venue = %opp.sell_venue,
+                        spread_pct = opp.spread.to_f64_lossy() * 100.0,
+                        profit_usd = opp.estimated_profit.to_f64_lossy(money::PRICE_DECIMALS),
+                        "opportunity"
+                    );
                 }
             }
         }
@@ -390,10 +602,10 @@ impl ArbitrageBot {
     // Execute arbitrage trades
     async fn execute_arbitrage(
         opportunities: Arc<Mutex<Vec<ArbitrageOpportunity>>>,
-        provider: Arc<Provider<Http>>,
-        wallet: LocalWallet,
+        execution_client: Arc<ExecutionClient<BotSigner>>,
         config: Config,
         metrics: Arc<Mutex<BotMetrics>>,
+        storage: Arc<storage::Storage>,
     ) {
         let mut interval = tokio::time::interval(Duration::from_millis(100));
         
@@ -406,30 +618,90 @@ impl ArbitrageBot {
             };
             
             if let Some(opp) = best_opportunity {
-                if opp.confidence > 0.8 && opp.estimated_profit > config.min_profit_threshold {
-                    println!("🚀 Executing arbitrage: {} -> {} | ${:.2} profit",
-                        opp.buy_venue, opp.sell_venue, opp.estimated_profit);
-                    
+                let min_profit = volatility::effective_min_profit(
+                    config.min_profit_threshold,
+                    opp.volatility,
+                    ESTIMATED_EXECUTION_LATENCY_SECS,
+                    config.volatility_multiplier,
+                );
+                if opp.confidence > 0.8 && opp.estimated_profit > min_profit {
+                    tracing::info!(
+                        buy_venue = %opp.buy_venue,
+                        sell_venue = %opp.sell_venue,
+                        profit_usd = opp.estimated_profit.to_f64_lossy(money::PRICE_DECIMALS),
+                        "executing arbitrage"
+                    );
+
                     let start_time = std::time::Instant::now();
-                    
-                    match Self::execute_trade(&opp, &provider, &wallet, &config).await {
+                    metrics::TOTAL_TRADES.inc();
+
+                    // Recorded before submission: a crash between this write
+                    // and the broadcast leaves a recoverable `Pending` row
+                    // instead of an untracked in-flight tx.
+                    let trade_key = match storage.record_trade_pending(&opp.id, opp.timestamp) {
+                        Ok(key) => Some(key),
+                        Err(e) => {
+                            tracing::warn!(error = %e, "failed to persist pending trade record");
+                            None
+                        }
+                    };
+
+                    match Self::execute_trade(&opp, &execution_client, &config).await {
                         Ok(tx_hash) => {
                             let execution_time = start_time.elapsed().as_millis() as f64;
-                            println!("✅ Trade executed successfully: 0x{:x}", tx_hash);
-                            
+                            tracing::info!(tx_hash = %format!("{:#x}", tx_hash), "trade executed successfully");
+                            metrics::SUCCESSFUL_TRADES.inc();
+                            metrics::GAS_USED.inc_by(opp.gas_estimate);
+
+                            if let Some(key) = &trade_key {
+                                if let Err(e) = storage.update_trade_status(
+                                    key,
+                                    &opp.id,
+                                    opp.timestamp,
+                                    storage::TradeStatus::Confirmed { tx_hash },
+                                ) {
+                                    tracing::warn!(error = %e, "failed to persist confirmed trade record");
+                                }
+                            }
+
                             // Update metrics
                             let mut m = metrics.lock().await;
                             m.total_trades += 1;
                             m.successful_trades += 1;
-                            m.total_profit += opp.estimated_profit;
-                            m.total_volume += opp.trade_size;
+                            m.total_profit = m.total_profit.checked_add(opp.estimated_profit).unwrap_or(m.total_profit);
+                            m.total_volume = m.total_volume.checked_add(opp.trade_size).unwrap_or(m.total_volume);
                             m.avg_execution_time = (m.avg_execution_time * (m.successful_trades - 1) as f64 + execution_time) / m.successful_trades as f64;
                             m.gas_used += opp.gas_estimate;
+                            metrics::TOTAL_PROFIT_USD.set(m.total_profit.to_f64_lossy(money::PRICE_DECIMALS));
+                            metrics::TOTAL_VOLUME_USD.set(m.total_volume.to_f64_lossy(money::PRICE_DECIMALS));
+                            metrics::AVG_EXECUTION_MS.set(m.avg_execution_time);
+                            if let Err(e) = storage.save_metrics(&m) {
+                                tracing::warn!(error = %e, "failed to persist metrics");
+                            }
                         }
                         Err(e) => {
-                            println!("❌ Trade execution failed: {}", e);
-                            let mut m = metrics.lock().await;
-                            m.total_trades += 1;
+                            tracing::warn!(error = %e, "trade execution failed");
+                            if let Some(key) = &trade_key {
+                                if let Err(persist_err) = storage.update_trade_status(
+                                    key,
+                                    &opp.id,
+                                    opp.timestamp,
+                                    storage::TradeStatus::Failed { reason: e.to_string() },
+                                ) {
+                                    tracing::warn!(error = %persist_err, "failed to persist failed trade record");
+                                }
+                            }
+                            {
+                                let mut m = metrics.lock().await;
+                                m.total_trades += 1;
+                                if let Err(e) = storage.save_metrics(&m) {
+                                    tracing::warn!(error = %e, "failed to persist metrics");
+                                }
+                            }
+                            if signer::is_device_disconnected(e.as_ref()) {
+                                tracing::warn!("hardware signer disconnected, pausing execution");
+                                tokio::time::sleep(Duration::from_secs(10)).await;
+                            }
                         }
                     }
                     
@@ -444,31 +716,35 @@ impl ArbitrageBot {
     // Execute individual trade on smart contract
     async fn execute_trade(
         opportunity: &ArbitrageOpportunity,
-        provider: &Arc<Provider<Http>>,
-        wallet: &LocalWallet,
+        execution_client: &Arc<ExecutionClient<BotSigner>>,
         config: &Config,
     ) -> Result<H256, Box<dyn std::error::Error>> {
         let contract_address: Address = config.contract_address.parse()?;
-        let client = SignerMiddleware::new(provider.clone(), wallet.clone());
-        
-        // Build transaction data for smart contract call
-        let gas_price = provider.get_gas_price().await?;
-        if gas_price.as_u64() > config.max_gas_price {
+
+        // The gas oracle middleware fills in maxFeePerGas/maxPriorityFeePerGas;
+        // `max_gas_price` is now a cap on the effective max fee it comes back
+        // with, not a legacy gas price we fetch ourselves.
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            execution_client.estimate_eip1559_fees(None).await?;
+        if max_fee_per_gas.as_u64() > config.max_gas_price {
             return Err("Gas price too high".into());
         }
-        
-        // Mock transaction (in production, encode actual contract call)
-        let tx = TransactionRequest::new()
+
+        // Mock transaction (in production, encode actual contract call). The
+        // nonce manager on `execution_client` assigns the nonce, so multiple
+        // in-flight trades can be submitted concurrently without colliding.
+        let tx = Eip1559TransactionRequest::new()
             .to(contract_address)
             .gas(U256::from(opportunity.gas_estimate))
-            .gas_price(gas_price)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(max_priority_fee_per_gas)
             .value(U256::zero());
-            
-        let tx_hash = client.send_transaction(tx, None).await?
+
+        let tx_hash = execution_client.send_transaction(tx, None).await?
             .await?
             .unwrap()
             .transaction_hash;
-            
+
         Ok(tx_hash)
     }
 
@@ -480,15 +756,21 @@ impl ArbitrageBot {
             interval.tick().await;
             
             let m = metrics.lock().await;
-            println!("\n📊 === ARBITRAGE BOT METRICS ===");
-            println!("Total Trades: {}", m.total_trades);
-            println!("Successful: {} ({:.1}%)", m.successful_trades,
-                if m.total_trades > 0 { (m.successful_trades as f64 / m.total_trades as f64) * 100.0 } else { 0.0 });
-            println!("Total Profit: ${:.2}", m.total_profit);
-            println!("Total Volume: ${:.2}", m.total_volume);
-            println!("Avg Execution: {:.1}ms", m.avg_execution_time);
-            println!("Gas Used: {}", m.gas_used);
-            println!("================================\n");
+            let success_rate = if m.total_trades > 0 {
+                (m.successful_trades as f64 / m.total_trades as f64) * 100.0
+            } else {
+                0.0
+            };
+            tracing::info!(
+                total_trades = m.total_trades,
+                successful_trades = m.successful_trades,
+                success_rate_pct = success_rate,
+                total_profit_usd = m.total_profit.to_f64_lossy(money::PRICE_DECIMALS),
+                total_volume_usd = m.total_volume.to_f64_lossy(money::PRICE_DECIMALS),
+                avg_execution_ms = m.avg_execution_time,
+                gas_used = m.gas_used,
+                "bot metrics"
+            );
         }
     }
 
@@ -502,18 +784,21 @@ impl ArbitrageBot {
             .take(5)
             .filter_map(|bid| {
                 if let [price, qty] = bid.as_array()?.as_slice() {
-                    Some((price.as_str()?.parse().ok()?, qty.as_str()?.parse().ok()?))
+                    // Binance sends quantities as decimal strings (e.g.
+                    // "0.00159000"), not raw integers, so they need the
+                    // decimal-aware parser rather than `Amount::from_str`.
+                    Some((price.as_str()?.parse().ok()?, money::parse_decimal_quantity(qty.as_str()?).ok()?))
                 } else { None }
             })
             .collect();
-            
+
         let asks = parsed["a"].as_array()
             .unwrap_or(&vec![])
             .iter()
             .take(5)
             .filter_map(|ask| {
                 if let [price, qty] = ask.as_array()?.as_slice() {
-                    Some((price.as_str()?.parse().ok()?, qty.as_str()?.parse().ok()?))
+                    Some((price.as_str()?.parse().ok()?, money::parse_decimal_quantity(qty.as_str()?).ok()?))
                 } else { None }
             })
             .collect();
@@ -530,20 +815,41 @@ impl ArbitrageBot {
         })
     }
     
-    fn calculate_confidence(spread_pct: f64, trade_size: f64) -> f64 {
+    fn calculate_confidence(spread_pct: FixedPrice, trade_size: Amount) -> f64 {
+        let half_pct: FixedPrice = "0.005".parse().expect("valid literal");
+        let one_pct: FixedPrice = "0.01".parse().expect("valid literal");
+
         let mut confidence = 0.5;
-        
-        if spread_pct > 0.5 { confidence += 0.2; }
-        if spread_pct > 1.0 { confidence += 0.1; }
-        if trade_size > 10000.0 { confidence += 0.1; }
-        if trade_size > 25000.0 { confidence += 0.1; }
-        
+
+        if spread_pct > half_pct { confidence += 0.2; }
+        if spread_pct > one_pct { confidence += 0.1; }
+        if trade_size > qty(10_000.0) { confidence += 0.1; }
+        if trade_size > qty(25_000.0) { confidence += 0.1; }
+
         confidence.min(1.0)
     }
+
+    /// Dump the persisted trade history as JSON, for post-hoc analysis.
+    fn trade_history_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let history = self.storage.trade_history()?;
+        Ok(serde_json::to_string_pretty(&history)?)
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `--json` emits one JSON object per log event for log aggregators;
+    // otherwise logs are human-readable on stdout. Either way, level is
+    // controlled by `RUST_LOG` (defaults to "info").
+    let json_logs = std::env::args().any(|a| a == "--json");
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    if json_logs {
+        tracing_subscriber::fmt().json().with_env_filter(env_filter).init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(env_filter).init();
+    }
+
     // Load configuration
     let config = Config {
         base_rpc_url: "https://mainnet.base.org".to_string(),
@@ -552,14 +858,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         contract_address: "0x1234567890123456789012345678901234567890".to_string(), // Deploy address
         binance_ws_url: "wss://stream.binance.com:9443/ws/ethusdc@depth".to_string(),
         backpack_ws_url: "wss://backpack-api.com/ws".to_string(), // Mock
-        min_profit_threshold: 50.0, // $50
+        min_profit_threshold: qty(50.0), // $50
         max_gas_price: 50_000_000_000, // 50 gwei
         max_slippage: 1.0, // 1%
+        signer_backend: SignerBackend::PrivateKey,
+        keystore_path: None,
+        ledger_derivation_path: None,
+        dex_pools: vec![
+            PoolConfig {
+                venue: "aerodrome-weth-usdc".to_string(),
+                pool_address: "0x0000000000000000000000000000000000000000".to_string(), // Deploy-specific
+                token_pair: "WETH/USDC".to_string(),
+                fee_bps: 30,
+                kind: PoolKind::Volatile,
+                token0_decimals: 18, // WETH
+                token1_decimals: USDC_DECIMALS,
+            },
+            PoolConfig {
+                venue: "aerodrome-usdc-usdbc".to_string(),
+                pool_address: "0x0000000000000000000000000000000000000000".to_string(), // Deploy-specific
+                token_pair: "USDC/USDbC".to_string(),
+                fee_bps: 4,
+                kind: PoolKind::Stable { amplification: 100 },
+                token0_decimals: USDC_DECIMALS,
+                token1_decimals: 6, // USDbC
+            },
+        ],
+        metrics_addr: "0.0.0.0:9898".parse().expect("valid socket addr literal"),
+        db_path: std::env::var("DB_PATH").unwrap_or_else(|_| "./arbitrage_bot.db".to_string()),
+        // Set SOCKS5_PROXY=127.0.0.1:9050 to run behind a local Tor daemon.
+        socks5_proxy: std::env::var("SOCKS5_PROXY").ok(),
+        volatility_multiplier: 0.5,
     };
-    
+
     // Initialize and run bot
     let bot = ArbitrageBot::new(config).await?;
+
+    // `--dump-trade-history` prints the persisted trade log as JSON and
+    // exits, instead of starting the monitoring/execution loops.
+    if std::env::args().any(|a| a == "--dump-trade-history") {
+        println!("{}", bot.trade_history_json()?);
+        return Ok(());
+    }
+
     bot.run().await?;
-    
+
     Ok(())
 }
\ No newline at end of file
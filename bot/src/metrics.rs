@@ -0,0 +1,96 @@
+//! Prometheus counters/gauges served over HTTP at `/metrics`.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, Gauge, GaugeVec, IntCounter, IntGauge, Opts, Registry, TextEncoder};
+
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+fn register_counter(name: &str, help: &str) -> IntCounter {
+    let counter = IntCounter::new(name, help).expect("valid metric name");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+}
+
+fn register_gauge(name: &str, help: &str) -> Gauge {
+    let gauge = Gauge::new(name, help).expect("valid metric name");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("metric registered once");
+    gauge
+}
+
+pub static TOTAL_TRADES: Lazy<IntCounter> =
+    Lazy::new(|| register_counter("arbitrage_total_trades", "Total trades attempted"));
+pub static SUCCESSFUL_TRADES: Lazy<IntCounter> =
+    Lazy::new(|| register_counter("arbitrage_successful_trades", "Total trades confirmed on-chain"));
+pub static OPPORTUNITIES_DETECTED: Lazy<IntCounter> = Lazy::new(|| {
+    register_counter(
+        "arbitrage_opportunities_detected_total",
+        "Arbitrage opportunities found since startup",
+    )
+});
+pub static GAS_USED: Lazy<IntCounter> =
+    Lazy::new(|| register_counter("arbitrage_gas_used_total", "Cumulative gas used by submitted trades"));
+
+pub static TOTAL_PROFIT_USD: Lazy<Gauge> =
+    Lazy::new(|| register_gauge("arbitrage_total_profit_usd", "Cumulative realized profit in USD"));
+pub static TOTAL_VOLUME_USD: Lazy<Gauge> =
+    Lazy::new(|| register_gauge("arbitrage_total_volume_usd", "Cumulative trade volume in USD"));
+pub static AVG_EXECUTION_MS: Lazy<Gauge> =
+    Lazy::new(|| register_gauge("arbitrage_avg_execution_ms", "Rolling average trade execution time"));
+
+pub static VENUE_SPREAD_PCT: Lazy<GaugeVec> = Lazy::new(|| {
+    let gauge = GaugeVec::new(
+        Opts::new(
+            "arbitrage_venue_spread_pct",
+            "Last observed spread percentage between a buy and sell venue",
+        ),
+        &["buy_venue", "sell_venue"],
+    )
+    .expect("valid metric spec");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("metric registered once");
+    gauge
+});
+
+/// Set from `detect_opportunities` after each scan, to the number of
+/// opportunities currently held in the shared `opportunities` list.
+pub static OPEN_OPPORTUNITIES: Lazy<IntGauge> = Lazy::new(|| {
+    let gauge = IntGauge::new(
+        "arbitrage_open_opportunities",
+        "Opportunities currently tracked awaiting execution",
+    )
+    .expect("valid metric name");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("metric registered once");
+    gauge
+});
+
+async fn handle_scrape(_req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let metric_families = REGISTRY.gather();
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&metric_families, &mut buffer) {
+        tracing::error!(error = %e, "failed to encode prometheus metrics");
+        return Ok(Response::builder().status(500).body(Body::empty()).unwrap());
+    }
+    Ok(Response::new(Body::from(buffer)))
+}
+
+/// Serve `/metrics` on `addr` until the process exits. Spawned once from
+/// `ArbitrageBot::run` alongside the other background tasks.
+pub async fn serve(addr: SocketAddr) {
+    let make_svc = make_service_fn(|_conn| async { Ok::<_, Infallible>(service_fn(handle_scrape)) });
+    tracing::info!(%addr, "serving prometheus metrics");
+    if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+        tracing::error!(error = %e, "metrics server failed");
+    }
+}
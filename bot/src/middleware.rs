@@ -0,0 +1,64 @@
+//! Shared EIP-1559/nonce-managed/signing middleware stack for execution,
+//! built once in `ArbitrageBot::new` instead of per-trade.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::middleware::gas_oracle::{GasOracle, GasOracleError, GasOracleMiddleware};
+use ethers::middleware::nonce_manager::NonceManagerMiddleware;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::Signer;
+use ethers::types::U256;
+
+/// `GasOracle` backed by the node's own `eth_feeHistory` RPC, since Base has
+/// no public gas-price API comparable to Etherscan's gas oracle. This pulls
+/// the same maxFeePerGas/maxPriorityFeePerGas pair
+/// `Middleware::estimate_eip1559_fees` would, but through the `GasOracle`
+/// trait so it plugs into `GasOracleMiddleware`.
+#[derive(Debug, Clone)]
+pub struct FeeHistoryGasOracle {
+    provider: Arc<Provider<Http>>,
+}
+
+impl FeeHistoryGasOracle {
+    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+        FeeHistoryGasOracle { provider }
+    }
+}
+
+#[async_trait]
+impl GasOracle for FeeHistoryGasOracle {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        let (max_fee, _max_priority_fee) = self.provider.estimate_eip1559_fees(None).await?;
+        Ok(max_fee)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        Ok(self.provider.estimate_eip1559_fees(None).await?)
+    }
+}
+
+/// The full client used for everything that signs and submits a
+/// transaction. Read-only price monitoring keeps using the plain
+/// `Provider<Http>` - only the execution path needs nonces and a signer.
+pub type ExecutionClient<S> =
+    SignerMiddleware<NonceManagerMiddleware<GasOracleMiddleware<Provider<Http>, FeeHistoryGasOracle>>, S>;
+
+/// Assemble the middleware stack once and share it (via `Arc`) across every
+/// concurrent trade, so the nonce manager can hand out sequential nonces
+/// for multiple in-flight arbitrage transactions without collisions.
+pub async fn build_execution_stack<S>(
+    provider: Arc<Provider<Http>>,
+    signer: S,
+) -> Result<Arc<ExecutionClient<S>>, Box<dyn std::error::Error>>
+where
+    S: Signer + Clone,
+{
+    let oracle = FeeHistoryGasOracle::new(Arc::clone(&provider));
+    let gas_managed = GasOracleMiddleware::new((*provider).clone(), oracle);
+    let address = signer.address();
+    let nonce_managed = NonceManagerMiddleware::new(gas_managed, address);
+    let signer_managed = SignerMiddleware::new(nonce_managed, signer);
+    Ok(Arc::new(signer_managed))
+}
@@ -0,0 +1,321 @@
+//! Fixed-point `U256` money types, replacing raw `f64` prices/sizes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ethers::types::U256;
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Canonical scale used for prices parsed off exchange feeds, so that two
+/// `FixedPrice` values built by this bot can be compared without
+/// renormalizing. DEX reserves and CEX book levels are both converted to
+/// this scale as soon as they're parsed.
+pub const PRICE_DECIMALS: u32 = 18;
+
+/// A quantity of token base units (e.g. wei for WETH, or USDC's 6-decimal
+/// units). Always an exact integer - never derived from a float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Amount(pub U256);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(U256::zero());
+
+    pub fn from_base_units(units: U256) -> Self {
+        Amount(units)
+    }
+
+    pub fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_add(rhs.0).map(Amount)
+    }
+
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> {
+        self.0.checked_sub(rhs.0).map(Amount)
+    }
+
+    pub fn min(self, rhs: Amount) -> Amount {
+        if self.0 < rhs.0 { self } else { rhs }
+    }
+
+    /// Multiply by a fixed-point price, e.g. `qty.checked_mul_price(p)` to
+    /// convert a token quantity into a quote-currency amount.
+    pub fn checked_mul_price(self, price: FixedPrice) -> Option<Amount> {
+        let scale = U256::from(10u64).pow(U256::from(price.decimals));
+        self.0
+            .checked_mul(price.mantissa)?
+            .checked_div(scale)
+            .map(Amount)
+    }
+
+    /// Lossy conversion for display/logging only - never feed the result
+    /// back into a calculation.
+    pub fn to_f64_lossy(self, decimals: u32) -> f64 {
+        let scale = 10f64.powi(decimals as i32);
+        self.0.as_u128() as f64 / scale
+    }
+
+    /// Rescale a raw token amount from `from_decimals` to `to_decimals`, e.g.
+    /// normalizing a 6-decimal USDC reserve up to `PRICE_DECIMALS` so it's
+    /// directly comparable to an 18-decimal WETH reserve.
+    pub fn rescale(self, from_decimals: u32, to_decimals: u32) -> Amount {
+        if to_decimals == from_decimals {
+            return self;
+        }
+        if to_decimals > from_decimals {
+            Amount(self.0 * U256::from(10u64).pow(U256::from(to_decimals - from_decimals)))
+        } else {
+            Amount(self.0 / U256::from(10u64).pow(U256::from(from_decimals - to_decimals)))
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A fixed-point decimal: `mantissa / 10^decimals`. Backed by a `U256`
+/// mantissa so prices with token-level precision (e.g. 18-decimal WETH
+/// quoted in 6-decimal USDC) round-trip losslessly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixedPrice {
+    pub mantissa: U256,
+    pub decimals: u32,
+}
+
+impl FixedPrice {
+    pub const fn new(mantissa: U256, decimals: u32) -> Self {
+        FixedPrice { mantissa, decimals }
+    }
+
+    /// Rescale to `PRICE_DECIMALS` so it can be compared against prices
+    /// parsed from other venues without the caller juggling scales.
+    pub fn normalized(self) -> Self {
+        self.rescale(PRICE_DECIMALS)
+    }
+
+    pub fn rescale(self, decimals: u32) -> Self {
+        if decimals == self.decimals {
+            return self;
+        }
+        let mantissa = if decimals > self.decimals {
+            self.mantissa * U256::from(10u64).pow(U256::from(decimals - self.decimals))
+        } else {
+            self.mantissa / U256::from(10u64).pow(U256::from(self.decimals - decimals))
+        };
+        FixedPrice { mantissa, decimals }
+    }
+
+    pub fn checked_sub(self, rhs: FixedPrice) -> Option<FixedPrice> {
+        let (a, b) = (self.normalized(), rhs.normalized());
+        a.mantissa
+            .checked_sub(b.mantissa)
+            .map(|m| FixedPrice::new(m, PRICE_DECIMALS))
+    }
+
+    pub fn checked_add(self, rhs: FixedPrice) -> Option<FixedPrice> {
+        let (a, b) = (self.normalized(), rhs.normalized());
+        a.mantissa
+            .checked_add(b.mantissa)
+            .map(|m| FixedPrice::new(m, PRICE_DECIMALS))
+    }
+
+    /// `(self - rhs) / rhs`, expressed in the same fixed-point scale as a
+    /// ratio (i.e. `0.01 @ 18 decimals` == 1%), so it never needs float
+    /// division and can't produce `NaN`.
+    pub fn relative_diff(self, rhs: FixedPrice) -> Option<FixedPrice> {
+        let (a, b) = (self.normalized(), rhs.normalized());
+        if b.mantissa.is_zero() {
+            return None;
+        }
+        let diff = a.mantissa.checked_sub(b.mantissa)?;
+        let scale = U256::from(10u64).pow(U256::from(PRICE_DECIMALS));
+        diff.checked_mul(scale)
+            .map(|m| FixedPrice::new(m / b.mantissa, PRICE_DECIMALS))
+    }
+
+    pub fn to_f64_lossy(self) -> f64 {
+        self.mantissa.as_u128() as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Exact `"int.frac"` decimal string for `mantissa / 10^decimals`, with
+    /// no intermediate `f64` - used by `Serialize` so persisted prices don't
+    /// lose precision the way `to_f64_lossy` would.
+    pub fn to_exact_decimal_string(self) -> String {
+        if self.decimals == 0 {
+            return self.mantissa.to_string();
+        }
+        let scale = U256::from(10u64).pow(U256::from(self.decimals));
+        let int_part = self.mantissa / scale;
+        let frac_part = (self.mantissa % scale).to_string();
+        let frac_padded = format!("{frac_part:0>width$}", width = self.decimals as usize);
+        format!("{int_part}.{frac_padded}")
+    }
+
+    /// `1 / self`, at the same decimal scale - e.g. turning a "quote per
+    /// base" price into a "base per quote" price.
+    pub fn invert(self) -> Option<FixedPrice> {
+        if self.mantissa.is_zero() {
+            return None;
+        }
+        let scale = U256::from(10u64).pow(U256::from(self.decimals));
+        Some(FixedPrice::new(scale.checked_mul(scale)? / self.mantissa, self.decimals))
+    }
+}
+
+impl PartialOrd for FixedPrice {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FixedPrice {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.normalized().mantissa.cmp(&other.normalized().mantissa)
+    }
+}
+
+impl fmt::Display for FixedPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.6}", self.to_f64_lossy())
+    }
+}
+
+/// Parses either a `"0x..."` hex string or a plain *integer* decimal string
+/// into a `U256` - i.e. a raw base-unit amount, not a human quantity like
+/// Binance's `"0.00159000"`. Use `parse_decimal_quantity` for those.
+fn parse_u256(s: &str) -> Result<U256, String> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        U256::from_str_radix(hex, 16).map_err(|e| e.to_string())
+    } else {
+        U256::from_dec_str(s).map_err(|e| e.to_string())
+    }
+}
+
+/// Parses a human decimal-quantity string (e.g. Binance's `"0.00159000"`)
+/// into an `Amount` scaled to `PRICE_DECIMALS`, the same way
+/// `FixedPrice::from_str` already scales decimal price strings - so a CEX
+/// book quantity ends up on the same scale as the normalized reserves it's
+/// sized/compared against, instead of erroring on the decimal point.
+pub fn parse_decimal_quantity(s: &str) -> Result<Amount, String> {
+    s.parse::<FixedPrice>().map(|p| Amount(p.normalized().mantissa))
+}
+
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_u256(s).map(Amount)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse::<Amount>().map_err(D::Error::custom)
+    }
+}
+
+/// A `FixedPrice` parsed from JSON at `PRICE_DECIMALS` - the shape most
+/// feed payloads arrive in (a plain decimal-string quote).
+impl FromStr for FixedPrice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let mantissa = U256::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+            return Ok(FixedPrice::new(mantissa, PRICE_DECIMALS));
+        }
+        let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+        let frac_padded = format!(
+            "{:0<width$}",
+            frac_part,
+            width = PRICE_DECIMALS as usize
+        );
+        let frac_padded = &frac_padded[..PRICE_DECIMALS as usize];
+        let combined = format!("{int_part}{frac_padded}");
+        let mantissa = U256::from_dec_str(&combined).map_err(|e| e.to_string())?;
+        Ok(FixedPrice::new(mantissa, PRICE_DECIMALS))
+    }
+}
+
+impl Serialize for FixedPrice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_exact_decimal_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for FixedPrice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse::<FixedPrice>().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_decimal_quantity_scales_a_binance_style_string() {
+        let qty = parse_decimal_quantity("0.00159000").unwrap();
+        assert_eq!(qty.0, U256::from(159u64) * U256::exp10(13)); // 0.00159 * 1e18
+    }
+
+    #[test]
+    fn fixed_price_serialize_round_trips_exactly_through_a_decimal_string() {
+        let p: FixedPrice = "123456789.123456789123456789".parse().unwrap();
+        let json = serde_json::to_string(&p).unwrap();
+        let round_tripped: FixedPrice = serde_json::from_str(&json).unwrap();
+        assert_eq!(p, round_tripped);
+    }
+
+    #[test]
+    fn amount_rescale_up_and_down() {
+        let usdc = Amount(U256::from(2_500_000u64)); // 2.5 USDC at 6 decimals
+        assert_eq!(usdc.rescale(6, 18).0, U256::from(2_500_000u64) * U256::exp10(12));
+        assert_eq!(usdc.rescale(6, 6).0, usdc.0);
+        assert_eq!(usdc.rescale(6, 0).0, U256::from(2u64));
+    }
+
+    #[test]
+    fn fixed_price_parses_decimal_and_hex() {
+        let p: FixedPrice = "2500.5".parse().unwrap();
+        assert_eq!(p.decimals, PRICE_DECIMALS);
+        assert!((p.to_f64_lossy() - 2500.5).abs() < 1e-9);
+
+        let hex: FixedPrice = "0x64".parse().unwrap();
+        assert_eq!(hex.mantissa, U256::from(100u64));
+    }
+
+    #[test]
+    fn fixed_price_rescale_round_trips() {
+        let p = FixedPrice::new(U256::from(100u64), 2); // 1.00
+        let rescaled = p.rescale(4);
+        assert_eq!(rescaled.mantissa, U256::from(10_000u64));
+        assert_eq!(rescaled.rescale(2), p);
+    }
+
+    #[test]
+    fn amount_checked_mul_price() {
+        let qty = Amount(U256::from(2u64) * U256::exp10(18)); // 2 tokens at 18dp
+        let price: FixedPrice = "10.0".parse().unwrap(); // $10 / token
+        let total = qty.checked_mul_price(price).unwrap();
+        assert_eq!(total.0, U256::from(20u64) * U256::exp10(18));
+    }
+
+    #[test]
+    fn relative_diff_matches_percentage() {
+        let a: FixedPrice = "110.0".parse().unwrap();
+        let b: FixedPrice = "100.0".parse().unwrap();
+        let diff = a.relative_diff(b).unwrap();
+        assert!((diff.to_f64_lossy() - 0.10).abs() < 1e-9);
+    }
+}
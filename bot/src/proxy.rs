@@ -0,0 +1,51 @@
+//! Optional SOCKS5 tunneling (e.g. a local Tor daemon) for outbound
+//! RPC/exchange connections.
+
+use reqwest::Url;
+use tokio::net::TcpStream;
+use tokio_socks::tcp::Socks5Stream;
+use tokio_tungstenite::{client_async_tls, connect_async, MaybeTlsStream, WebSocketStream};
+
+/// Builds the shared HTTP client used for both REST calls and (wrapped in
+/// an `ethers::providers::Http` transport) the RPC provider, so the proxy
+/// only needs to be configured in one place.
+pub fn build_http_client(socks5_proxy: Option<&str>) -> Result<reqwest::Client, Box<dyn std::error::Error>> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy_addr) = socks5_proxy {
+        let proxy = reqwest::Proxy::all(format!("socks5h://{proxy_addr}"))?;
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder.build()?)
+}
+
+/// Splits a `ws://`/`wss://` URL into `(host, port)`, since `tokio_socks`
+/// dials a bare address rather than parsing the scheme itself.
+fn websocket_host_port(url: &str) -> Result<(String, u16), Box<dyn std::error::Error>> {
+    let parsed: Url = url.parse()?;
+    let host = parsed.host_str().ok_or("websocket url has no host")?.to_string();
+    let port = parsed
+        .port_or_known_default()
+        .ok_or("websocket url has no port and no default for its scheme")?;
+    Ok((host, port))
+}
+
+/// Connects a WebSocket, tunneling through `socks5_proxy` when set (dialing
+/// the SOCKS5 connection first, then completing the TLS/WebSocket handshake
+/// over that tunneled stream) and dialing directly otherwise.
+pub async fn connect_websocket(
+    url: &str,
+    socks5_proxy: Option<&str>,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, Box<dyn std::error::Error>> {
+    match socks5_proxy {
+        Some(proxy_addr) => {
+            let (host, port) = websocket_host_port(url)?;
+            let tcp = Socks5Stream::connect(proxy_addr, (host.as_str(), port)).await?;
+            let (ws_stream, _) = client_async_tls(url, tcp.into_inner()).await?;
+            Ok(ws_stream)
+        }
+        None => {
+            let (ws_stream, _) = connect_async(url).await?;
+            Ok(ws_stream)
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Key-custody backends (raw private key, JSON keystore, Ledger) behind
+//! ethers' `Signer` trait.
+
+use std::fmt;
+
+use async_trait::async_trait;
+use ethers::signers::{Ledger, LedgerError, LocalWallet, Signer, WalletError};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Signature};
+
+use crate::Config;
+
+/// Which key-custody backend to use, set via `Config::signer_backend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerBackend {
+    /// `PRIVATE_KEY` env var, parsed directly into a `LocalWallet`.
+    PrivateKey,
+    /// An encrypted JSON keystore file, password from env or a prompt.
+    Keystore,
+    /// A Ledger hardware wallet over USB.
+    Ledger,
+}
+
+#[derive(Clone)]
+pub enum BotSigner {
+    /// Both the raw-private-key and decrypted-keystore paths produce a
+    /// `LocalWallet` - they only differ in how the key material is sourced.
+    Local(LocalWallet),
+    Ledger(Ledger),
+}
+
+#[derive(Debug)]
+pub enum BotSignerError {
+    Wallet(WalletError),
+    Ledger(LedgerError),
+}
+
+impl fmt::Display for BotSignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BotSignerError::Wallet(e) => write!(f, "wallet signer error: {e}"),
+            BotSignerError::Ledger(e) => write!(f, "ledger signer error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BotSignerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BotSignerError::Wallet(e) => Some(e),
+            BotSignerError::Ledger(e) => Some(e),
+        }
+    }
+}
+
+impl From<WalletError> for BotSignerError {
+    fn from(e: WalletError) -> Self {
+        BotSignerError::Wallet(e)
+    }
+}
+
+impl From<LedgerError> for BotSignerError {
+    fn from(e: LedgerError) -> Self {
+        BotSignerError::Ledger(e)
+    }
+}
+
+#[async_trait]
+impl Signer for BotSigner {
+    type Error = BotSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            BotSigner::Local(w) => Ok(w.sign_message(message).await?),
+            BotSigner::Ledger(l) => Ok(l.sign_message(message).await?),
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            BotSigner::Local(w) => Ok(w.sign_transaction(tx).await?),
+            BotSigner::Ledger(l) => Ok(l.sign_transaction(tx).await?),
+        }
+    }
+
+    async fn sign_typed_data<T: ethers::types::transaction::eip712::Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            BotSigner::Local(w) => Ok(w
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| BotSignerError::Wallet(e))?),
+            BotSigner::Ledger(l) => Ok(l
+                .sign_typed_data(payload)
+                .await
+                .map_err(BotSignerError::Ledger)?),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            BotSigner::Local(w) => w.address(),
+            BotSigner::Ledger(l) => l.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            BotSigner::Local(w) => w.chain_id(),
+            BotSigner::Ledger(l) => l.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            BotSigner::Local(w) => BotSigner::Local(w.with_chain_id(chain_id)),
+            BotSigner::Ledger(l) => BotSigner::Ledger(l.with_chain_id(chain_id)),
+        }
+    }
+}
+
+/// Build the configured signer backend. All three paths yield a `BotSigner`
+/// with the Base mainnet chain ID already set, so callers never branch on
+/// which backend is live.
+pub async fn build_signer(config: &Config) -> Result<BotSigner, Box<dyn std::error::Error>> {
+    const BASE_CHAIN_ID: u64 = 8453;
+
+    match config.signer_backend {
+        SignerBackend::PrivateKey => {
+            let wallet: LocalWallet = config.private_key.parse()?;
+            Ok(BotSigner::Local(wallet.with_chain_id(BASE_CHAIN_ID)))
+        }
+        SignerBackend::Keystore => {
+            let path = config
+                .keystore_path
+                .as_ref()
+                .ok_or("keystore signer selected but keystore_path is not set")?;
+            let password = match std::env::var("KEYSTORE_PASSWORD") {
+                Ok(p) => p,
+                Err(_) => rpassword::prompt_password("Keystore password: ")?,
+            };
+            let wallet = LocalWallet::decrypt_keystore(path, password)?;
+            Ok(BotSigner::Local(wallet.with_chain_id(BASE_CHAIN_ID)))
+        }
+        SignerBackend::Ledger => {
+            let derivation_path = config
+                .ledger_derivation_path
+                .clone()
+                .unwrap_or_else(|| "m/44'/60'/0'/0/0".to_string());
+            let ledger = Ledger::new(
+                ethers::signers::HDPath::Other(derivation_path),
+                BASE_CHAIN_ID,
+            )
+            .await?;
+            Ok(BotSigner::Ledger(ledger))
+        }
+    }
+}
+
+/// Walks the error's `source()` chain looking for any Ledger signer error
+/// (`BotSignerError::Ledger`), so the execution loop can pause (rather than
+/// busy-retry every 100ms) when the hardware wallet drops off USB. A
+/// disconnect is not told apart from other Ledger failures; all of them
+/// return `true`.
+pub fn is_device_disconnected(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(e) = current {
+        if let Some(BotSignerError::Ledger(_)) = e.downcast_ref::<BotSignerError>() {
+            return true;
+        }
+        current = e.source();
+    }
+    false
+}
@@ -0,0 +1,257 @@
+//! Price-impact-aware sizing for the DEX leg of a dex-to-cex arbitrage,
+//! walking the CEX book against the AMM curve instead of assuming a flat
+//! `liquidity * 0.1` fill.
+
+use ethers::types::U256;
+
+use crate::amm::{self, PoolKind};
+use crate::money::{Amount, FixedPrice, PRICE_DECIMALS};
+
+/// Closed-form optimal input against a *constant* external price `P`
+/// (i.e. one infinitely-deep level), quoted in input units per output unit
+/// (the same direction as `Rin/Rout`): the point where the AMM's marginal
+/// cost of output after the trade, fee included, would equal `P`.
+///
+/// `dx* = (sqrt(Rin*Rout*(1-f)*P) - Rin) / (1-f)`, clamped to `>= 0`.
+pub fn optimal_size_constant_price(
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+    target_price: FixedPrice,
+) -> Option<U256> {
+    if fee_bps >= 10_000 {
+        return None;
+    }
+    let fee_factor = U256::from(10_000 - fee_bps); // (1-f), in bps
+    let price_scale = U256::from(10u64).pow(U256::from(PRICE_DECIMALS));
+    let price = target_price.normalized().mantissa;
+
+    let under_sqrt = reserve_in
+        .checked_mul(reserve_out)?
+        .checked_mul(fee_factor)?
+        .checked_div(U256::from(10_000u64))?
+        .checked_mul(price)?
+        .checked_div(price_scale)?;
+    let sqrt_term = under_sqrt.integer_sqrt();
+
+    if sqrt_term <= reserve_in {
+        return Some(U256::zero());
+    }
+    sqrt_term
+        .checked_sub(reserve_in)?
+        .checked_mul(U256::from(10_000u64))?
+        .checked_div(fee_factor)
+}
+
+/// Bisection analogue of `optimal_size_constant_price` for StableSwap pools,
+/// which have no closed form: the inverse of `amm::stableswap_spot_price`
+/// (input units per output unit) after a trade of size `dx` is
+/// monotonically increasing in `dx`, so binary search for the point where
+/// it crosses `target_price`.
+fn optimal_size_stable_price(
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+    amplification: u64,
+    target_price: FixedPrice,
+) -> Option<U256> {
+    if fee_bps >= 10_000 {
+        return None;
+    }
+    let mut lo = U256::zero();
+    let mut hi = reserve_in.checked_mul(U256::from(100u64))?;
+    for _ in 0..128 {
+        if lo >= hi {
+            break;
+        }
+        let mid = lo + (hi - lo) / U256::from(2u64);
+        let Some(dy) = amm::stableswap_output(mid, reserve_in, reserve_out, amplification, fee_bps) else {
+            hi = mid;
+            continue;
+        };
+        if dy >= reserve_out {
+            hi = mid;
+            continue;
+        }
+        let new_reserve_in = reserve_in.checked_add(mid)?;
+        let new_reserve_out = reserve_out.checked_sub(dy)?;
+        let Some(marginal) = amm::stableswap_spot_price(new_reserve_in, new_reserve_out, amplification)
+            .and_then(FixedPrice::invert)
+        else {
+            break;
+        };
+        if marginal < target_price {
+            lo = mid + U256::one();
+        } else {
+            hi = mid;
+        }
+    }
+    Some(lo)
+}
+
+/// Dispatches to the closed-form (`Volatile`) or bisection (`Stable`)
+/// optimal-size solver for `kind`'s curve.
+fn optimal_size(
+    reserve_in: U256,
+    reserve_out: U256,
+    fee_bps: u32,
+    target_price: FixedPrice,
+    kind: PoolKind,
+) -> Option<U256> {
+    match kind {
+        PoolKind::Volatile => optimal_size_constant_price(reserve_in, reserve_out, fee_bps, target_price),
+        PoolKind::Stable { amplification } => {
+            optimal_size_stable_price(reserve_in, reserve_out, fee_bps, amplification, target_price)
+        }
+    }
+}
+
+/// Result of walking a CEX book against an AMM curve for the buy (DEX) leg
+/// of a dex-to-cex arbitrage: spend `amm_input` (quote currency) into the
+/// pool, receive `amm_output` (base currency), sell `amm_output` into the
+/// book for `proceeds` (quote currency).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SizedTrade {
+    pub amm_input: Amount,
+    pub amm_output: Amount,
+    pub proceeds: Amount,
+}
+
+impl SizedTrade {
+    /// `proceeds - amm_input`, before gas. `None` only on a genuine
+    /// underflow (loss), which the caller should treat as "don't trade".
+    pub fn gross_profit(&self) -> Option<Amount> {
+        self.proceeds.checked_sub(self.amm_input)
+    }
+}
+
+/// Walk `bids` (best price first, quantities in base currency) against an
+/// AMM pool's `(quote_reserve, base_reserve)`, filling each level up to the
+/// point where the AMM's post-trade marginal price would cross that
+/// level's price, then moving to the next level - stopping the moment the
+/// AMM is no longer cheaper than the best remaining bid. `kind` selects
+/// which curve the pool trades under.
+pub fn size_against_book(
+    quote_reserve: U256,
+    base_reserve: U256,
+    fee_bps: u32,
+    kind: PoolKind,
+    bids: &[(FixedPrice, Amount)],
+) -> SizedTrade {
+    let mut cur_quote = quote_reserve;
+    let mut cur_base = base_reserve;
+    let mut total = SizedTrade::default();
+
+    for &(bid_price, bid_depth) in bids {
+        let Some(marginal) = amm::spot_price(cur_base, cur_quote, kind) else {
+            break;
+        };
+        if marginal >= bid_price {
+            break;
+        }
+
+        // The bid is already quote (input) per base (output), the direction
+        // `optimal_size` takes its target in.
+        let Some(mut dx_quote) = optimal_size(cur_quote, cur_base, fee_bps, bid_price, kind) else {
+            break;
+        };
+        if dx_quote.is_zero() {
+            break;
+        }
+
+        let Some(mut dy_base) = amm::swap_output(dx_quote, cur_quote, cur_base, fee_bps, kind) else {
+            break;
+        };
+
+        let exhausted_level = dy_base >= bid_depth.0;
+        if exhausted_level {
+            dy_base = bid_depth.0;
+            let Some(capped_dx) = amm::swap_input_for_output(dy_base, cur_quote, cur_base, fee_bps, kind)
+            else {
+                break;
+            };
+            dx_quote = capped_dx;
+        }
+
+        let Some(proceeds) = Amount(dy_base).checked_mul_price(bid_price) else {
+            break;
+        };
+
+        cur_quote = match cur_quote.checked_add(dx_quote) {
+            Some(v) => v,
+            None => break,
+        };
+        cur_base = match cur_base.checked_sub(dy_base) {
+            Some(v) => v,
+            None => break,
+        };
+
+        total.amm_input = total.amm_input.checked_add(Amount(dx_quote)).unwrap_or(total.amm_input);
+        total.amm_output = total.amm_output.checked_add(Amount(dy_base)).unwrap_or(total.amm_output);
+        total.proceeds = total.proceeds.checked_add(proceeds).unwrap_or(total.proceeds);
+
+        if !exhausted_level {
+            // Crossed mid-level: every remaining (lower-priced) level is
+            // worse than where we just stopped, so there's nothing left.
+            break;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimal_size_constant_price_is_zero_when_pool_already_past_target() {
+        // Pool marginal price (quote/base) is already above the target, so
+        // there's no profitable size.
+        let reserve_in = U256::from(1000u64) * U256::exp10(18);
+        let reserve_out = U256::from(900u64) * U256::exp10(18);
+        let target: FixedPrice = "0.5".parse().unwrap();
+        let dx = optimal_size_constant_price(reserve_in, reserve_out, 30, target).unwrap();
+        assert_eq!(dx, U256::zero());
+    }
+
+    #[test]
+    fn optimal_size_constant_price_pushes_marginal_price_toward_target() {
+        let reserve_in = U256::from(1000u64) * U256::exp10(18);
+        let reserve_out = U256::from(1000u64) * U256::exp10(18);
+        let target: FixedPrice = "1.1".parse().unwrap();
+        let dx = optimal_size_constant_price(reserve_in, reserve_out, 0, target).unwrap();
+        assert!(!dx.is_zero());
+
+        let dy = amm::constant_product_output(dx, reserve_in, reserve_out, 0).unwrap();
+        let new_in = reserve_in + dx;
+        let new_out = reserve_out - dy;
+        // Marginal price in the target's direction: input per output, `Rin/Rout`.
+        let marginal = amm::constant_product_spot_price(new_out, new_in).unwrap();
+        assert!((marginal.to_f64_lossy() - 1.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn size_against_book_stops_once_amm_is_no_longer_cheaper() {
+        let reserve_in = U256::from(1000u64) * U256::exp10(18);
+        let reserve_out = U256::from(1000u64) * U256::exp10(18);
+        let bids = vec![
+            ("1.2".parse::<FixedPrice>().unwrap(), Amount(U256::from(50u64) * U256::exp10(18))),
+            ("0.5".parse::<FixedPrice>().unwrap(), Amount(U256::from(50u64) * U256::exp10(18))),
+        ];
+        let sized = size_against_book(reserve_out, reserve_in, 0, PoolKind::Volatile, &bids);
+        // The AMM's starting marginal price (1.0) is below the first bid
+        // (1.2) but above the second (0.5), so only the first level fills.
+        assert!(!sized.amm_input.0.is_zero());
+        assert!(sized.gross_profit().unwrap().0 > U256::zero());
+    }
+
+    #[test]
+    fn size_against_book_walks_stable_curve_for_stable_pools() {
+        let reserve_in = U256::from(1_000_000u64) * U256::exp10(18);
+        let reserve_out = U256::from(1_000_000u64) * U256::exp10(18);
+        let bids = vec![("1.01".parse::<FixedPrice>().unwrap(), Amount(U256::from(1000u64) * U256::exp10(18)))];
+        let sized = size_against_book(reserve_out, reserve_in, 0, PoolKind::Stable { amplification: 100 }, &bids);
+        assert!(!sized.amm_input.0.is_zero());
+    }
+}
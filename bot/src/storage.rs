@@ -0,0 +1,116 @@
+//! Embedded `sled` persistence for opportunities, trade history, and
+//! cumulative metrics, so they survive a restart.
+
+use std::sync::Arc;
+
+use ethers::types::H256;
+use serde::{Deserialize, Serialize};
+use sled::Db;
+
+use crate::ArbitrageOpportunity;
+use crate::BotMetrics;
+
+const METRICS_KEY: &[u8] = b"metrics";
+
+/// Lifecycle of a trade, recorded *before* submission so a crash mid-flight
+/// leaves a `Pending` row behind instead of an untracked in-flight tx.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TradeStatus {
+    Pending,
+    Submitted { tx_hash: H256 },
+    Confirmed { tx_hash: H256 },
+    Failed { reason: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub opportunity_id: String,
+    pub timestamp: u64,
+    pub status: TradeStatus,
+}
+
+#[derive(Clone)]
+pub struct Storage {
+    db: Db,
+}
+
+fn timestamp_key(prefix: &str, timestamp: u64, id: &str) -> Vec<u8> {
+    format!("{prefix}:{timestamp:020}:{id}").into_bytes()
+}
+
+impl Storage {
+    pub fn open(path: &str) -> Result<Arc<Self>, Box<dyn std::error::Error>> {
+        let db = sled::open(path)?;
+        Ok(Arc::new(Storage { db }))
+    }
+
+    /// Record a detected opportunity for post-hoc analysis. Best-effort:
+    /// callers log a warning on failure rather than treating it as fatal,
+    /// since a missed audit row shouldn't stop the bot from trading.
+    pub fn record_opportunity(&self, opp: &ArbitrageOpportunity) -> Result<(), Box<dyn std::error::Error>> {
+        let key = timestamp_key("opportunity", opp.timestamp, &opp.id);
+        let value = serde_json::to_vec(opp)?;
+        self.db.insert(key, value)?;
+        Ok(())
+    }
+
+    /// Writes the `Pending` row *before* the transaction is submitted, so a
+    /// crash between this call and the actual broadcast still leaves a
+    /// recoverable trail. Returns the key so the caller can update it once
+    /// the outcome is known.
+    pub fn record_trade_pending(
+        &self,
+        opportunity_id: &str,
+        timestamp: u64,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let key = timestamp_key("trade", timestamp, opportunity_id);
+        let record = TradeRecord {
+            opportunity_id: opportunity_id.to_string(),
+            timestamp,
+            status: TradeStatus::Pending,
+        };
+        self.db.insert(&key, serde_json::to_vec(&record)?)?;
+        Ok(key)
+    }
+
+    pub fn update_trade_status(
+        &self,
+        key: &[u8],
+        opportunity_id: &str,
+        timestamp: u64,
+        status: TradeStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let record = TradeRecord {
+            opportunity_id: opportunity_id.to_string(),
+            timestamp,
+            status,
+        };
+        self.db.insert(key, serde_json::to_vec(&record)?)?;
+        Ok(())
+    }
+
+    /// Reload cumulative metrics on startup so profit/volume counters survive
+    /// a restart instead of resetting to zero.
+    pub fn load_metrics(&self) -> Result<BotMetrics, Box<dyn std::error::Error>> {
+        match self.db.get(METRICS_KEY)? {
+            Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            None => Ok(BotMetrics::default()),
+        }
+    }
+
+    pub fn save_metrics(&self, metrics: &BotMetrics) -> Result<(), Box<dyn std::error::Error>> {
+        self.db.insert(METRICS_KEY, serde_json::to_vec(metrics)?)?;
+        Ok(())
+    }
+
+    /// Dump the full trade history, oldest first, for post-hoc analysis
+    /// (e.g. serving it over a query endpoint as JSON).
+    pub fn trade_history(&self) -> Result<Vec<TradeRecord>, Box<dyn std::error::Error>> {
+        let mut records = Vec::new();
+        for entry in self.db.scan_prefix(b"trade:") {
+            let (_, value) = entry?;
+            records.push(serde_json::from_slice(&value)?);
+        }
+        Ok(records)
+    }
+}
@@ -0,0 +1,136 @@
+//! Volatility-aware spread/profit gating: an EWMA of absolute log-returns
+//! per token pair, widening the minimum spread/profit thresholds when
+//! prices are moving fast.
+
+use std::collections::HashMap;
+
+use crate::money::{Amount, FixedPrice};
+
+/// RiskMetrics-style EWMA decay - recent returns dominate, but volatility
+/// doesn't vanish the instant the market goes quiet for one tick.
+const EWMA_LAMBDA: f64 = 0.94;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PairState {
+    last_price: Option<f64>,
+    ewma_abs_log_return: f64,
+}
+
+/// Per-key rolling volatility tracker, fed one price observation per
+/// `update` call. `detect_opportunities` feeds it each DEX venue's price
+/// once per detection tick, keyed by venue and token pair; CEX prices are
+/// not fed in.
+#[derive(Debug, Default)]
+pub struct VolatilityTracker {
+    pairs: HashMap<String, PairState>,
+}
+
+impl VolatilityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a new price observation for `token_pair`, returning the updated
+    /// EWMA of absolute log-returns (a unitless fractional volatility, e.g.
+    /// `0.002` == a typical 0.2% move per update).
+    pub fn update(&mut self, token_pair: &str, price: f64) -> f64 {
+        let state = self.pairs.entry(token_pair.to_string()).or_default();
+
+        let abs_log_return = match state.last_price {
+            Some(last) if last > 0.0 && price > 0.0 => (price / last).ln().abs(),
+            _ => 0.0,
+        };
+        state.last_price = Some(price);
+
+        state.ewma_abs_log_return =
+            EWMA_LAMBDA * state.ewma_abs_log_return + (1.0 - EWMA_LAMBDA) * abs_log_return;
+        state.ewma_abs_log_return
+    }
+}
+
+/// `1 + multiplier * volatility * (1 + latency_secs)`, the scale factor
+/// applied to both the minimum spread and the minimum profit threshold.
+fn threshold_factor(volatility: f64, latency_secs: f64, multiplier: f64) -> f64 {
+    1.0 + multiplier * volatility * (1.0 + latency_secs)
+}
+
+/// Widen `base_min_spread` by the volatility-plus-adverse-selection buffer.
+/// Falls back to `base_min_spread` unchanged if the computed buffer can't be
+/// parsed (e.g. a pathological `NaN` multiplier from misconfiguration).
+pub fn effective_min_spread(
+    base_min_spread: FixedPrice,
+    volatility: f64,
+    latency_secs: f64,
+    multiplier: f64,
+) -> FixedPrice {
+    let factor = threshold_factor(volatility, latency_secs, multiplier);
+    let buffer = base_min_spread.to_f64_lossy() * (factor - 1.0);
+    match format!("{buffer:.18}").parse::<FixedPrice>() {
+        Ok(buffer_price) => base_min_spread.checked_add(buffer_price).unwrap_or(base_min_spread),
+        Err(_) => base_min_spread,
+    }
+}
+
+/// Scale `base_min_profit` by the same volatility-plus-adverse-selection
+/// factor as `effective_min_spread`, so a trade needs proportionally more
+/// edge to clear the bar exactly when prices are moving fast.
+pub fn effective_min_profit(
+    base_min_profit: Amount,
+    volatility: f64,
+    latency_secs: f64,
+    multiplier: f64,
+) -> Amount {
+    let factor = threshold_factor(volatility, latency_secs, multiplier);
+    match format!("{factor:.18}").parse::<FixedPrice>() {
+        Ok(factor_price) => base_min_profit.checked_mul_price(factor_price).unwrap_or(base_min_profit),
+        Err(_) => base_min_profit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_update_reports_zero_volatility() {
+        let mut tracker = VolatilityTracker::new();
+        assert_eq!(tracker.update("WETH/USDC", 2500.0), 0.0);
+    }
+
+    #[test]
+    fn ewma_increases_after_a_large_move_and_decays_after_calm_ticks() {
+        let mut tracker = VolatilityTracker::new();
+        tracker.update("WETH/USDC", 2500.0);
+        let after_jump = tracker.update("WETH/USDC", 2750.0); // +10% move
+        assert!(after_jump > 0.0);
+
+        let mut after_calm = after_jump;
+        for _ in 0..50 {
+            after_calm = tracker.update("WETH/USDC", 2750.0); // no further movement
+        }
+        assert!(after_calm < after_jump);
+    }
+
+    #[test]
+    fn tracks_pairs_independently() {
+        let mut tracker = VolatilityTracker::new();
+        tracker.update("WETH/USDC", 2500.0);
+        tracker.update("WETH/USDC", 2750.0);
+        // A different pair's first observation is unaffected by WETH/USDC's history.
+        assert_eq!(tracker.update("BTC/USDC", 60_000.0), 0.0);
+    }
+
+    #[test]
+    fn effective_min_spread_widens_with_volatility() {
+        let base: FixedPrice = "0.001".parse().unwrap();
+        let widened = effective_min_spread(base, 0.05, 2.0, 0.5);
+        assert!(widened > base);
+    }
+
+    #[test]
+    fn effective_min_profit_scales_with_volatility() {
+        let base = Amount(ethers::types::U256::from(50_000_000u64)); // $50 at 6dp
+        let scaled = effective_min_profit(base, 0.05, 2.0, 0.5);
+        assert!(scaled.0 > base.0);
+    }
+}